@@ -0,0 +1,128 @@
+use std::path::Path;
+
+/// 一行 INI 内容及其上下文。`raw` 保留原始字节（含行内注释、缩进、
+/// `\r` 等），保证未改动时 round-trip 与原文件逐字节一致。
+struct LineEntry {
+    raw: String,
+    section: String,
+    /// 若该行是 `key = value`，记录其原始 key 文本（保留大小写）。
+    key: Option<String>,
+}
+
+/// 保留注释、空行与节顺序的 INI 读改写管理器。
+///
+/// 解析时记录每一行的原文与所属节；`set`/`remove` 仅重写受影响的那一行，
+/// 新键追加到所在节末尾，缺失的节追加到文件末尾。未做任何修改时，
+/// `serialize` 的输出与输入完全一致。
+pub struct ConfigStore {
+    lines: Vec<LineEntry>,
+}
+
+impl ConfigStore {
+    /// 解析内存中的 INI 文本。
+    pub fn parse(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut section = String::new();
+        for raw in content.split('\n') {
+            let trimmed = raw.trim();
+            let mut key = None;
+            if trimmed.starts_with('[') && trimmed.ends_with(']') && trimmed.len() >= 2 {
+                section = trimmed[1..trimmed.len() - 1].to_string();
+            } else if !trimmed.is_empty() && !trimmed.starts_with(';') && !trimmed.starts_with('#')
+            {
+                if let Some((k, _)) = trimmed.split_once('=') {
+                    key = Some(k.trim().to_string());
+                }
+            }
+            lines.push(LineEntry { raw: raw.to_string(), section: section.clone(), key });
+        }
+        ConfigStore { lines }
+    }
+
+    /// 从磁盘加载；文件不存在时返回空 store。
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(ConfigStore { lines: Vec::new() })
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn matches(entry: &LineEntry, section: &str, key: &str) -> bool {
+        entry.section.eq_ignore_ascii_case(section)
+            && entry.key.as_deref().map(|k| k.eq_ignore_ascii_case(key)).unwrap_or(false)
+    }
+
+    /// 读取某节某键的值（取最后一次出现），不存在则 `None`。
+    pub fn get(&self, section: &str, key: &str) -> Option<String> {
+        self.lines
+            .iter()
+            .rev()
+            .find(|e| Self::matches(e, section, key))
+            .and_then(|e| e.raw.trim().split_once('=').map(|(_, v)| v.trim().to_string()))
+    }
+
+    /// 读取某个键的值，忽略其所属节（取最后一次出现）。用于无固定分节的文件。
+    pub fn get_any(&self, key: &str) -> Option<String> {
+        self.lines
+            .iter()
+            .rev()
+            .find(|e| e.key.as_deref().map(|k| k.eq_ignore_ascii_case(key)).unwrap_or(false))
+            .and_then(|e| e.raw.trim().split_once('=').map(|(_, v)| v.trim().to_string()))
+    }
+
+    /// 设置某节某键的值：命中则重写该行，否则追加到节末尾（缺节则追加新节）。
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        if let Some(entry) = self.lines.iter_mut().find(|e| Self::matches(e, section, key)) {
+            let name = entry.key.clone().unwrap_or_else(|| key.to_string());
+            entry.raw = format!("{} = {}", name, value);
+            return;
+        }
+
+        // 节存在：插入到该节最后一行之后。
+        if let Some(idx) = self
+            .lines
+            .iter()
+            .rposition(|e| e.section.eq_ignore_ascii_case(section))
+        {
+            self.lines.insert(
+                idx + 1,
+                LineEntry {
+                    raw: format!("{} = {}", key, value),
+                    section: section.to_string(),
+                    key: Some(key.to_string()),
+                },
+            );
+            return;
+        }
+
+        // 节不存在：追加新节到文件末尾。
+        self.lines.push(LineEntry {
+            raw: format!("[{}]", section),
+            section: section.to_string(),
+            key: None,
+        });
+        self.lines.push(LineEntry {
+            raw: format!("{} = {}", key, value),
+            section: section.to_string(),
+            key: Some(key.to_string()),
+        });
+    }
+
+    /// 删除某节某键的所有出现。
+    pub fn remove(&mut self, section: &str, key: &str) {
+        self.lines.retain(|e| !Self::matches(e, section, key));
+    }
+
+    /// 还原为文本；未改动时与原文件逐字节一致。
+    pub fn serialize(&self) -> String {
+        self.lines.iter().map(|e| e.raw.as_str()).collect::<Vec<_>>().join("\n")
+    }
+
+    /// 写回磁盘。
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.serialize()).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,152 @@
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Local, NaiveDate};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::config::ConfigStore;
+
+/// 随包发布的激活服务器 ed25519 公钥（32 字节），用于验签响应。
+const ACTIVATION_PUBLIC_KEY: &[u8] = include_bytes!("../keys/activation_public.key");
+
+/// 激活服务器对机器码的签名响应。
+#[derive(Debug, Deserialize)]
+struct ActivationResponse {
+    user_type: String,
+    auth_end: String,
+    signature: String,
+}
+
+/// `verify_license` 的结果。
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LicenseResult {
+    /// 服务器校验通过且未过期。
+    Valid { user_type: String, auth_end: String },
+    /// 已过授权到期日。
+    Expired { auth_end: String },
+    /// 服务器判定该机器码已被吊销。
+    RevokedMachine,
+    /// 服务器不可达，处于宽限期内（含剩余天数）。
+    Offline { remaining_days: i64 },
+}
+
+/// 默认宽限期（天），服务器不可达时允许离线运行。
+const DEFAULT_GRACE_DAYS: i64 = 7;
+
+/// 解析 `YYYY-MM-DD`。
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+/// 用内置公钥验证激活响应对 `payload`（即 `user_type|auth_end`）的 ed25519
+/// 签名。任一步骤失败（公钥/签名格式错误或验签不通过）都返回 `false`。
+fn verify_signature(payload: &str, signature_b64: &str) -> bool {
+    let key_bytes: [u8; 32] = match ACTIVATION_PUBLIC_KEY.try_into() {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    let key = match VerifyingKey::from_bytes(&key_bytes) {
+        Ok(k) => k,
+        Err(_) => return false,
+    };
+    let sig_bytes = match general_purpose::STANDARD.decode(signature_b64) {
+        Ok(b) => b,
+        Err(_) => return false,
+    };
+    let sig = match Signature::from_slice(&sig_bytes) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    key.verify(payload.as_bytes(), &sig).is_ok()
+}
+
+/// 在线校验授权：向激活服务器 POST 机器码，验签后写回权威的
+/// `user_type`/`auth_end`，并按系统日期判断是否过期。
+///
+/// 服务器不可达时落入宽限期（`Offline`，携带剩余天数），超出宽限期后
+/// 同样视为不可用。所有错误均以类型化结果返回，不会 panic。
+#[tauri::command]
+pub async fn verify_license(app: tauri::AppHandle) -> Result<LicenseResult, String> {
+    let path = app
+        .path()
+        .resolve("resources/config.ini", tauri::path::BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    let mut store = ConfigStore::load(&path)?;
+
+    let machine_code = store
+        .get("AUTH", "machine_code")
+        .ok_or_else(|| "错误: 配置中缺少 machine_code".to_string())?;
+    let server_url = store
+        .get("AUTH", "server_url")
+        .ok_or_else(|| "错误: 配置中缺少 [AUTH] server_url".to_string())?;
+    let grace_days = store
+        .get("AUTH", "grace_days")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_GRACE_DAYS);
+
+    let today = Local::now().date_naive();
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(&server_url)
+        .json(&serde_json::json!({ "machine_code": machine_code }))
+        .send()
+        .await;
+
+    let resp = match resp.and_then(|r| r.error_for_status()) {
+        Ok(r) => r,
+        Err(_) => return Ok(offline_result(&store, today, grace_days)),
+    };
+
+    let body: ActivationResponse = match resp.json().await {
+        Ok(b) => b,
+        Err(_) => return Ok(offline_result(&store, today, grace_days)),
+    };
+
+    // 先验签，再解释响应内容：未签名/伪造的响应一律拒绝，不得驱动结果。
+    let payload = format!("{}|{}", body.user_type, body.auth_end);
+    if !verify_signature(&payload, &body.signature) {
+        return Err("错误: 激活响应签名无效".to_string());
+    }
+
+    if body.user_type.eq_ignore_ascii_case("revoked") {
+        return Ok(LicenseResult::RevokedMachine);
+    }
+
+    // 写回权威值并记录本次校验时间戳（宽限期基准）。
+    store.set("AUTH", "auth_type", &body.user_type);
+    store.set("AUTH", "auth_end", &body.auth_end);
+    store.set("AUTH", "last_verified", &today.format("%Y-%m-%d").to_string());
+    store.save(&path)?;
+
+    match parse_date(&body.auth_end) {
+        Some(end) if end < today => Ok(LicenseResult::Expired { auth_end: body.auth_end }),
+        _ => Ok(LicenseResult::Valid {
+            user_type: body.user_type,
+            auth_end: body.auth_end,
+        }),
+    }
+}
+
+/// 服务器不可达时，依据上次成功校验时间计算剩余宽限天数。
+///
+/// 同时强制本地 `auth_end`：即便仍在宽限期内，若授权已到期也返回
+/// `Expired`，避免过期授权借离线宽限继续使用。
+fn offline_result(store: &ConfigStore, today: NaiveDate, grace_days: i64) -> LicenseResult {
+    if let Some(auth_end) = store.get("AUTH", "auth_end") {
+        if let Some(end) = parse_date(&auth_end) {
+            if end < today {
+                return LicenseResult::Expired { auth_end };
+            }
+        }
+    }
+
+    let remaining = store
+        .get("AUTH", "last_verified")
+        .and_then(|v| parse_date(&v))
+        .map(|last| grace_days - (today - last).num_days())
+        .unwrap_or(0)
+        .max(0);
+    LicenseResult::Offline { remaining_days: remaining }
+}
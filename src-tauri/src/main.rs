@@ -2,12 +2,18 @@
 
 use tauri::{
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
     tray::TrayIconBuilder,
     Manager,
 };
 use ico::{IconDir};
 
+mod config;
+mod license;
+mod shortcuts;
+mod updater;
+mod watcher;
+
 use std::fs;
 use serde::Serialize;
 use base64::{engine::general_purpose, Engine as _};
@@ -22,8 +28,8 @@ fn get_ini_content(app: tauri::AppHandle) -> Result<String, String> {
     fs::read_to_string(resource_path).map_err(|e| e.to_string())
 }
 
-#[derive(Serialize)]
-struct AuthInfo {
+#[derive(Serialize, Clone)]
+pub struct AuthInfo {
     machine_code: String,
     user_type_display: String,
     auth_end: String,
@@ -36,32 +42,14 @@ fn get_auth_info(app: tauri::AppHandle) -> Result<AuthInfo, String> {
         .resolve("resources/config.ini", tauri::path::BaseDirectory::Resource)
         .map_err(|e| e.to_string())?;
     let content = fs::read_to_string(resource_path).map_err(|e| e.to_string())?;
+    Ok(parse_auth_info(&content))
+}
 
-    let mut in_auth = false;
-    let mut machine_code: Option<String> = None;
-    let mut auth_type: Option<String> = None;
-    let mut auth_end: Option<String> = None;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with('[') {
-            in_auth = trimmed.eq_ignore_ascii_case("[AUTH]");
-            continue;
-        }
-        if !in_auth || trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
-            continue;
-        }
-        if let Some((k, v)) = trimmed.split_once('=') {
-            let key = k.trim().to_lowercase();
-            let val = v.trim().to_string();
-            match key.as_str() {
-                "machine_code" => machine_code = Some(val),
-                "auth_type" => auth_type = Some(val),
-                "auth_end" => auth_end = Some(val),
-                _ => {}
-            }
-        }
-    }
+pub fn parse_auth_info(content: &str) -> AuthInfo {
+    let store = config::ConfigStore::parse(content);
+    let machine_code = store.get("AUTH", "machine_code");
+    let auth_type = store.get("AUTH", "auth_type");
+    let auth_end = store.get("AUTH", "auth_end");
 
     let user_type_display = match auth_type.as_deref() {
         Some("free") => "免费用户".to_string(),
@@ -70,15 +58,15 @@ fn get_auth_info(app: tauri::AppHandle) -> Result<AuthInfo, String> {
         None => "(未找到)".to_string(),
     };
 
-    Ok(AuthInfo {
+    AuthInfo {
         machine_code: machine_code.unwrap_or_else(|| "(未找到)".to_string()),
         user_type_display,
         auth_end: auth_end.unwrap_or_else(|| "(未找到)".to_string()),
-    })
+    }
 }
 
-#[derive(Serialize)]
-struct Announcement {
+#[derive(Serialize, Clone)]
+pub struct Announcement {
     title: String,
     content: String,
 }
@@ -90,27 +78,16 @@ fn get_announcement(app: tauri::AppHandle) -> Result<Announcement, String> {
         .resolve("resources/announcement.ini", tauri::path::BaseDirectory::Resource)
         .map_err(|e| e.to_string())?;
     let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Ok(parse_announcement(&content))
+}
 
-    let mut title = String::from("(无标题)");
-    let mut body = String::from("(无内容)");
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
-            continue;
-        }
-        if let Some((k, v)) = trimmed.split_once('=') {
-            let key = k.trim().to_lowercase();
-            let val = v.trim();
-            match key.as_str() {
-                "title" => title = val.to_string(),
-                "content" => body = val.to_string(),
-                _ => {}
-            }
-        }
+pub fn parse_announcement(content: &str) -> Announcement {
+    // 与基线一致：公告键不限定分节，即使文件带有节头也能读到。
+    let store = config::ConfigStore::parse(content);
+    Announcement {
+        title: store.get_any("title").unwrap_or_else(|| "(无标题)".to_string()),
+        content: store.get_any("content").unwrap_or_else(|| "(无内容)".to_string()),
     }
-
-    Ok(Announcement { title, content: body })
 }
 
 #[tauri::command]
@@ -124,7 +101,7 @@ fn get_advertisement_data_url(app: tauri::AppHandle) -> Result<String, String> {
     Ok(format!("data:image/png;base64,{}", encoded))
 }
 
-fn extract_db_date_display(content: &str) -> String {
+pub fn extract_db_date_display(content: &str) -> String {
     let mut last: Option<&str> = None;
     for line in content.lines() {
         let t = line.trim();
@@ -180,78 +157,13 @@ fn resolve_tdx_ini_path() -> PathBuf {
 }
 
 fn read_tdx_dir_from_ini(ini_path: &Path) -> Option<String> {
-    let content = fs::read_to_string(ini_path).ok()?;
-    let mut in_paths = false;
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            let sect = &trimmed[1..trimmed.len()-1];
-            in_paths = sect.eq_ignore_ascii_case("Paths");
-            continue;
-        }
-        if in_paths {
-            if let Some((k, v)) = trimmed.split_once('=') {
-                if k.trim().eq_ignore_ascii_case("TDX_Directory") {
-                    return Some(v.trim().to_string());
-                }
-            }
-        }
-    }
-    None
+    config::ConfigStore::load(ini_path).ok()?.get("Paths", "TDX_Directory")
 }
 
 fn write_tdx_dir_to_ini(ini_path: &Path, new_dir: &str) -> Result<(), String> {
-    let line_new = format!("TDX_Directory = {}", new_dir);
-    if ini_path.exists() {
-        let content = fs::read_to_string(ini_path).map_err(|e| e.to_string())?;
-        let mut out = String::new();
-        let mut in_paths = false;
-        let mut found_paths = false;
-        let mut updated_key = false;
-
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with('[') && trimmed.ends_with(']') {
-                if in_paths && !updated_key {
-                    out.push_str(&line_new);
-                    out.push('\n');
-                }
-                let sect = &trimmed[1..trimmed.len()-1];
-                in_paths = sect.eq_ignore_ascii_case("Paths");
-                if in_paths { found_paths = true; }
-                out.push_str(line);
-                out.push('\n');
-                continue;
-            }
-            if in_paths {
-                if let Some((k, _)) = trimmed.split_once('=') {
-                    if k.trim().eq_ignore_ascii_case("TDX_Directory") {
-                        out.push_str(&line_new);
-                        out.push('\n');
-                        updated_key = true;
-                        continue;
-                    }
-                }
-            }
-            out.push_str(line);
-            out.push('\n');
-        }
-
-        if in_paths && !updated_key {
-            out.push_str(&line_new);
-            out.push('\n');
-        }
-        if !found_paths {
-            out.push_str("\n[Paths]\n");
-            out.push_str(&line_new);
-            out.push('\n');
-        }
-        fs::write(ini_path, out).map_err(|e| e.to_string())?
-    } else {
-        let out = format!("[Paths]\n{}\n", line_new);
-        fs::write(ini_path, out).map_err(|e| e.to_string())?
-    }
-    Ok(())
+    let mut store = config::ConfigStore::load(ini_path)?;
+    store.set("Paths", "TDX_Directory", new_dir);
+    store.save(ini_path)
 }
 
 #[tauri::command]
@@ -291,6 +203,79 @@ fn set_new_tdx_path(new_path: String) -> Result<TdxPathStatus, String> {
     }
 }
 
+/// 构建随授权/同步状态变化的托盘菜单。
+///
+/// 顶部为一个禁用的状态摘要（用户类型、授权到期、数据库日期），其下为
+/// 分隔符与 About，再接原有的操作项。状态读取失败时以占位文案兜底，
+/// 不会导致菜单构建失败。
+fn build_tray_menu(app: &tauri::AppHandle) -> Result<Menu<tauri::Wry>, Box<dyn std::error::Error>> {
+    let auth = app
+        .path()
+        .resolve("resources/config.ini", tauri::path::BaseDirectory::Resource)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|c| parse_auth_info(&c));
+
+    let db_date = app
+        .path()
+        .resolve("resources/sync_log.ini", tauri::path::BaseDirectory::Resource)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|c| extract_db_date_display(&c))
+        .unwrap_or_default();
+
+    let header_text = match auth {
+        Some(a) => {
+            let mut t = format!("{} | 到期: {}", a.user_type_display, a.auth_end);
+            let date = db_date.trim();
+            if !date.is_empty() {
+                t.push_str(&format!("\n{}", date));
+            }
+            t
+        }
+        None => "授权信息不可用".to_string(),
+    };
+
+    let header = MenuItemBuilder::new(header_text).id("status").enabled(false).build(app)?;
+    let about = PredefinedMenuItem::about(app, Some("About"), None)?;
+    let sep = PredefinedMenuItem::separator(app)?;
+
+    let quit = MenuItemBuilder::new("Quit").id("quit").build(app)?;
+    let hide = MenuItemBuilder::new("Hide").id("hide").build(app)?;
+    let show = MenuItemBuilder::new("Show").id("show").build(app)?;
+    let settings = MenuItemBuilder::new("Settings").id("settings").build(app)?;
+    let update = MenuItemBuilder::new("Check for updates").id("update").build(app)?;
+
+    let menu = MenuBuilder::new(app)
+        .items(&[&header, &sep, &about, &settings, &update, &quit, &hide, &show])
+        .build()?;
+    Ok(menu)
+}
+
+/// 重建并重新设置托盘菜单，使同步后无需重启即可刷新授权/数据库摘要。
+#[tauri::command]
+fn refresh_tray_menu(app: tauri::AppHandle) -> Result<(), String> {
+    let tray = app
+        .tray_by_id("main")
+        .ok_or_else(|| "错误: 未找到托盘图标".to_string())?;
+    let menu = build_tray_menu(&app).map_err(|e| e.to_string())?;
+    tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 持久化任意配置项到 `config.ini`，供设置窗口保存语言、快捷键、更新通道等，
+/// 无需为每个键编写专门的 Rust 代码。保留文件原有注释与排版。
+#[tauri::command]
+fn set_config_value(app: tauri::AppHandle, section: String, key: String, value: String) -> Result<(), String> {
+    let path = app
+        .path()
+        .resolve("resources/config.ini", tauri::path::BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    let mut store = config::ConfigStore::load(&path)?;
+    store.set(&section, &key, &value);
+    store.save(&path)
+}
+
 fn main() {
     run().expect("Failed to run application");
 }
@@ -298,19 +283,16 @@ fn main() {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let app = tauri::Builder::default()
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .setup(|app| {
-            let quit = MenuItemBuilder::new("Quit").id("quit").build(app)?;
-            let hide = MenuItemBuilder::new("Hide").id("hide").build(app)?;
-            let show = MenuItemBuilder::new("Show").id("show").build(app)?;
-            let settings = MenuItemBuilder::new("Settings").id("settings").build(app)?;
-            let tray_menu = MenuBuilder::new(app).items(&[&settings, &quit, &hide, &show]).build()?;
+            let tray_menu = build_tray_menu(app.handle())?;
 
             let icon_bytes = include_bytes!("../icons/icon.ico");
             let icon_dir = IconDir::read(std::io::Cursor::new(icon_bytes))?;
             let entry = icon_dir.entries().get(0).unwrap();
             let image = Image::new_owned(entry.decode()?.rgba_data().to_vec(), entry.width(), entry.height());
 
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id("main")
                 .icon(image)
                 .menu(&tray_menu)
                 .on_menu_event(move |app, event| {
@@ -335,11 +317,20 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                                 let _ = window.set_focus();
                             }
                         }
+                        "update" => {
+                            let handle = app.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let _ = updater::check_for_update(handle).await;
+                            });
+                        }
                         _ => {}
                     }
                 })
                 .build(app)?;
 
+            watcher::spawn(app.handle());
+            shortcuts::register(app.handle());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -349,7 +340,11 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             get_advertisement_data_url,
             get_sync_log,
             ensure_tdx_path_configured,
-            set_new_tdx_path
+            set_new_tdx_path,
+            updater::check_for_update,
+            refresh_tray_menu,
+            set_config_value,
+            license::verify_license
         ])
         .build(tauri::generate_context!())?;
 
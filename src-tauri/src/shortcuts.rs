@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::config::ConfigStore;
+
+/// 从 `config.ini` 的 `[Shortcuts]` 段读取某个键的加速键字符串。
+fn read_accelerator(app: &tauri::AppHandle, key: &str) -> Option<String> {
+    let path = app
+        .path()
+        .resolve("resources/config.ini", tauri::path::BaseDirectory::Resource)
+        .ok()?;
+    let val = ConfigStore::load(&path).ok()?.get("Shortcuts", key)?;
+    if val.is_empty() {
+        None
+    } else {
+        Some(val)
+    }
+}
+
+/// 切换主窗口可见性：可见则隐藏，否则显示并聚焦。
+fn toggle_main(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// 显示并聚焦设置窗口。
+fn open_settings(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// 注册 `[Shortcuts]` 中配置的全局快捷键。
+///
+/// 空值或解析失败的绑定会被跳过并记录日志，不影响启动。
+pub fn register(app: &tauri::AppHandle) {
+    let bindings: &[(&str, fn(&tauri::AppHandle))] = &[
+        ("toggle_main", toggle_main),
+        ("open_settings", open_settings),
+    ];
+
+    let gs = app.global_shortcut();
+    for (key, action) in bindings {
+        let accel = match read_accelerator(app, key) {
+            Some(a) => a,
+            None => continue,
+        };
+        let shortcut = match Shortcut::from_str(&accel) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("shortcuts: 无法解析 {} = {:?}: {}", key, accel, e);
+                continue;
+            }
+        };
+        let action = *action;
+        if let Err(e) = gs.on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                action(app);
+            }
+        }) {
+            eprintln!("shortcuts: 注册 {} ({}) 失败: {}", key, accel, e);
+        }
+    }
+}
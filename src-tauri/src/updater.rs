@@ -0,0 +1,133 @@
+use std::fs;
+use std::io::Write as _;
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+/// 远端发布清单，托管在更新服务器上。
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpdateManifest {
+    pub version: String,
+    pub notes: String,
+    pub url: String,
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// `check_for_update` 的返回结果，供前端区分三种情况。
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UpdateStatus {
+    /// 发现新版本，已开始下载并 emit `update-available`。
+    Available { version: String, notes: String },
+    /// 当前已是最新版本。
+    UpToDate { version: String },
+}
+
+/// 下载进度事件，随 `update-progress` 持续发送。
+#[derive(Debug, Serialize, Clone)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: u64,
+}
+
+/// 将 `"1.2.3"` 解析为 `(major, minor, patch)`，缺省位按 0 处理。
+fn parse_version(v: &str) -> (u64, u64, u64) {
+    let mut it = v.trim().split('.').map(|p| p.trim().parse::<u64>().unwrap_or(0));
+    (it.next().unwrap_or(0), it.next().unwrap_or(0), it.next().unwrap_or(0))
+}
+
+/// 清单版本是否比当前版本更新（major/minor/patch 元组比较）。
+fn is_newer(manifest: &str, current: &str) -> bool {
+    parse_version(manifest) > parse_version(current)
+}
+
+/// 版本号是否为纯 `d.d.d`（1~3 段十进制数字）。
+///
+/// 清单中的 `version` 不可信，会被拼进临时文件名；这里拒绝任何含路径
+/// 分隔符或非数字字符的值，防止 `../` 之类的路径穿越写入。
+fn is_clean_version(v: &str) -> bool {
+    let segs: Vec<&str> = v.split('.').collect();
+    (1..=3).contains(&segs.len())
+        && segs.iter().all(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// 清单地址，后续可迁移到 `[Updater]` 配置项。
+const MANIFEST_URL: &str = "https://update.tauri-tray-app.example/manifest.json";
+
+/// 检查更新：拉取远端清单并与当前版本比较。
+///
+/// 清单不可达时返回类型化错误（字符串形式），不会 panic；已是最新时返回
+/// `UpToDate`；发现新版本时下载安装包到临时目录并 emit `update-available`。
+#[tauri::command]
+pub async fn check_for_update(app: tauri::AppHandle) -> Result<UpdateStatus, String> {
+    let current = app.package_info().version.to_string();
+
+    let resp = reqwest::get(MANIFEST_URL)
+        .await
+        .map_err(|e| format!("错误: 无法连接更新服务器\n{}", e))?;
+    let manifest: UpdateManifest = resp
+        .json()
+        .await
+        .map_err(|e| format!("错误: 更新清单解析失败\n{}", e))?;
+
+    if !is_newer(&manifest.version, &current) {
+        return Ok(UpdateStatus::UpToDate { version: current });
+    }
+    if !is_clean_version(&manifest.version) {
+        return Err(format!("错误: 更新清单版本号非法: {:?}", manifest.version));
+    }
+
+    let installer = download_installer(&app, &manifest).await?;
+    if !manifest.signature.is_empty() {
+        verify_signature(&installer, &manifest.signature)?;
+    } else {
+        eprintln!(
+            "updater: 清单未提供签名，跳过校验——安装包 {} 未经验证",
+            installer.display()
+        );
+    }
+
+    app.emit("update-available", &manifest)
+        .map_err(|e| e.to_string())?;
+
+    Ok(UpdateStatus::Available {
+        version: manifest.version,
+        notes: manifest.notes,
+    })
+}
+
+/// 下载安装包到临时目录，并通过 `update-progress` 事件报告进度。
+async fn download_installer(
+    app: &tauri::AppHandle,
+    manifest: &UpdateManifest,
+) -> Result<std::path::PathBuf, String> {
+    let mut resp = reqwest::get(&manifest.url)
+        .await
+        .map_err(|e| format!("错误: 无法下载安装包\n{}", e))?;
+    let total = resp.content_length().unwrap_or(0);
+
+    let dest = std::env::temp_dir().join(format!("tauri-tray-app-{}-setup.exe", manifest.version));
+    let mut file = fs::File::create(&dest).map_err(|e| e.to_string())?;
+    let mut downloaded = 0u64;
+
+    while let Some(chunk) = resp.chunk().await.map_err(|e| e.to_string())? {
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        downloaded += chunk.len() as u64;
+        let _ = app.emit("update-progress", DownloadProgress { downloaded, total });
+    }
+
+    Ok(dest)
+}
+
+/// 校验安装包的分离式签名。
+///
+/// 签名算法尚未接入打包流程。对一个**携带**签名的下载而言，无法验签就等同于
+/// 验签失败——此处 fail-closed 返回 `Err`，绝不让未经验证的可执行文件悄悄通过。
+fn verify_signature(installer: &std::path::Path, _signature: &str) -> Result<(), String> {
+    // TODO: 接入与打包流程一致的签名算法（minisign / RSA）后改为真正校验。
+    Err(format!(
+        "错误: 安装包签名校验尚未实现，拒绝未验证的下载: {}",
+        installer.display()
+    ))
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use globset::{Glob, GlobSetBuilder};
+use notify::{RecursiveMode, Watcher};
+use tauri::{Emitter, Manager};
+
+use crate::{extract_db_date_display, parse_announcement, parse_auth_info};
+
+/// 监听的文件模式，限定在 resources 目录内。
+const PATTERNS: &[&str] = &["*.ini", "advertisement.png"];
+
+/// 合并同一批写入的去抖时长（编辑器常分多步落盘）。
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 在 `setup` 中启动：对 resources 目录做递归监听，按模式过滤后
+/// 去抖，再重新解析受影响的文件并 emit 对应的类型化事件。
+///
+/// 监听线程的任何失败都只记录日志，不会影响应用启动。
+pub fn spawn(app: &tauri::AppHandle) {
+    let resources = match app
+        .path()
+        .resolve("resources", tauri::path::BaseDirectory::Resource)
+    {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("watcher: 无法解析 resources 目录: {}", e);
+            return;
+        }
+    };
+
+    let mut builder = GlobSetBuilder::new();
+    for pat in PATTERNS {
+        match Glob::new(pat) {
+            Ok(g) => {
+                builder.add(g);
+            }
+            Err(e) => eprintln!("watcher: 无效的 glob 模式 {}: {}", pat, e),
+        }
+    }
+    let globset = match builder.build() {
+        Ok(g) => g,
+        Err(e) => {
+            eprintln!("watcher: 构建 glob 集合失败: {}", e);
+            return;
+        }
+    };
+
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(tx) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("watcher: 创建监听器失败: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&resources, RecursiveMode::Recursive) {
+            eprintln!("watcher: 监听 {} 失败: {}", resources.display(), e);
+            return;
+        }
+
+        // 去抖：记录每个文件最近一次事件时间，达到静默期后再处理。
+        let mut pending: Vec<(std::path::PathBuf, Instant)> = Vec::new();
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    for path in event.paths {
+                        if path
+                            .file_name()
+                            .map(|n| globset.is_match(Path::new(n)))
+                            .unwrap_or(false)
+                        {
+                            pending.retain(|(p, _)| p != &path);
+                            pending.push((path, Instant::now()));
+                        }
+                    }
+                }
+                Ok(Err(e)) => eprintln!("watcher: 监听事件错误: {}", e),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            let now = Instant::now();
+            let mut i = 0;
+            while i < pending.len() {
+                if now.duration_since(pending[i].1) >= DEBOUNCE {
+                    let (path, _) = pending.remove(i);
+                    emit_for(&handle, &path);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    });
+}
+
+/// 重新解析单个文件并 emit 对应事件。
+fn emit_for(app: &tauri::AppHandle, path: &Path) {
+    let name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(n) => n,
+        None => return,
+    };
+    match name {
+        "config.ini" => {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                let _ = app.emit("config-changed", parse_auth_info(&content));
+            }
+        }
+        "sync_log.ini" => {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                let prefix = extract_db_date_display(&content);
+                let _ = app.emit("sync-log-changed", format!("{}{}", prefix, content));
+            }
+        }
+        "announcement.ini" => {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                let _ = app.emit("announcement-changed", parse_announcement(&content));
+            }
+        }
+        _ => {}
+    }
+}